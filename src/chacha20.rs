@@ -0,0 +1,104 @@
+//! Hand-rolled ChaCha20 (RFC 8439, 20 rounds), used by the proc-macro at
+//! compile time to encrypt literals when `LITCRYPT_CIPHER=chacha20` is set.
+//!
+//! The exact same block function is re-emitted into the generated
+//! `litcrypt_internal` module (see [`crate::use_litcrypt`]) so that the
+//! caller's binary can decrypt without pulling in a runtime dependency.
+
+use std::convert::TryInto;
+
+const CONSTANTS: [u32; 4] = [0x61707865, 0x3320646e, 0x79622d32, 0x6b206574];
+
+#[inline(always)]
+fn quarter_round(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+	state[a] = state[a].wrapping_add(state[b]);
+	state[d] ^= state[a];
+	state[d] = state[d].rotate_left(16);
+
+	state[c] = state[c].wrapping_add(state[d]);
+	state[b] ^= state[c];
+	state[b] = state[b].rotate_left(12);
+
+	state[a] = state[a].wrapping_add(state[b]);
+	state[d] ^= state[a];
+	state[d] = state[d].rotate_left(8);
+
+	state[c] = state[c].wrapping_add(state[d]);
+	state[b] ^= state[c];
+	state[b] = state[b].rotate_left(7);
+}
+
+/// Produces one 64-byte keystream block for `key`/`nonce` at the given
+/// 32-bit block `counter`.
+pub fn block(key: &[u8; 32], nonce: &[u8; 12], counter: u32) -> [u8; 64] {
+	let mut state = [0u32; 16];
+	state[0..4].copy_from_slice(&CONSTANTS);
+	for i in 0..8 {
+		state[4 + i] = u32::from_le_bytes(key[i * 4..i * 4 + 4].try_into().unwrap());
+	}
+	state[12] = counter;
+	for i in 0..3 {
+		state[13 + i] = u32::from_le_bytes(nonce[i * 4..i * 4 + 4].try_into().unwrap());
+	}
+
+	let mut working = state;
+	for _ in 0..10 {
+		quarter_round(&mut working, 0, 4, 8, 12);
+		quarter_round(&mut working, 1, 5, 9, 13);
+		quarter_round(&mut working, 2, 6, 10, 14);
+		quarter_round(&mut working, 3, 7, 11, 15);
+
+		quarter_round(&mut working, 0, 5, 10, 15);
+		quarter_round(&mut working, 1, 6, 11, 12);
+		quarter_round(&mut working, 2, 7, 8, 13);
+		quarter_round(&mut working, 3, 4, 9, 14);
+	}
+
+	let mut out = [0u8; 64];
+	for i in 0..16 {
+		let word = working[i].wrapping_add(state[i]);
+		out[i * 4..i * 4 + 4].copy_from_slice(&word.to_le_bytes());
+	}
+	out
+}
+
+/// XORs `data` against the ChaCha20 keystream for `key`/`nonce`, starting at
+/// block counter `0`.
+pub fn xor(data: &[u8], key: &[u8; 32], nonce: &[u8; 12]) -> Vec<u8> {
+	let mut out = Vec::with_capacity(data.len());
+	for (counter, chunk) in data.chunks(64).enumerate() {
+		let keystream = block(key, nonce, counter as u32);
+		for (byte, ks) in chunk.iter().zip(keystream.iter()) {
+			out.push(byte ^ ks);
+		}
+	}
+	out
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use expectest::prelude::*;
+
+	#[test]
+	fn block_is_deterministic_and_key_dependent() {
+		let nonce = [0u8; 12];
+		expect!(block(&[1u8; 32], &nonce, 0)).to(be_equal_to(block(&[1u8; 32], &nonce, 0)));
+		expect!(block(&[1u8; 32], &nonce, 0)).to_not(be_equal_to(block(&[2u8; 32], &nonce, 0)));
+	}
+
+	#[test]
+	fn xor_is_its_own_inverse_across_multiple_blocks() {
+		let key = [7u8; 32];
+		let nonce = [9u8; 12];
+		// Long enough to span more than one 64-byte keystream block, so the
+		// counter actually increments.
+		let original = vec![0x42u8; 200];
+
+		let encrypted = xor(&original, &key, &nonce);
+		expect!(encrypted.clone()).to_not(be_equal_to(original.clone()));
+
+		let decrypted = xor(&encrypted, &key, &nonce);
+		expect!(decrypted).to(be_equal_to(original));
+	}
+}
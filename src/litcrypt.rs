@@ -6,6 +6,7 @@
 extern crate expectest;
 extern crate proc_macro;
 extern crate proc_macro2;
+extern crate proc_macro_crate;
 extern crate quote;
 extern crate rand;
 extern crate syn;
@@ -13,11 +14,13 @@ extern crate syn;
 use std::env;
 
 use proc_macro::{TokenStream, TokenTree};
-use proc_macro2::Literal;
+use proc_macro2::{Literal, TokenStream as TokenStream2};
+use proc_macro_crate::{crate_name, FoundCrate};
 use quote::quote;
 use rand::{rngs::OsRng, RngCore};
 use syn::{parse_macro_input, Expr, ExprGroup, ExprLit, Lit};
 
+mod chacha20;
 mod xor;
 
 lazy_static::lazy_static! {
@@ -28,6 +31,87 @@ lazy_static::lazy_static! {
 	};
 }
 
+lazy_static::lazy_static! {
+	// Generated once per compilation, just like `RAND_SPELL`, so that every
+	// `lc!` invocation in the crate embeds the same nonce base.
+	static ref NONCE_BASE: [u8; 8] = {
+		let mut base = [0u8; 8];
+		OsRng.fill_bytes(&mut base);
+		base
+	};
+}
+
+lazy_static::lazy_static! {
+	/// A fresh, independent 256-bit ChaCha20 key generated once per
+	/// compilation, the same way `RAND_SPELL` is — and, unlike `RAND_SPELL`,
+	/// *never* derived from `LITCRYPT_ENCRYPT_KEY`/the magic spell.
+	///
+	/// The magic spell is typically a short, human-chosen string; repeating
+	/// it to fill 32 bytes (the way XOR mode already stretches it to a key
+	/// via [`xor::xor`]) would leave the *effective* strength of the key at
+	/// whatever the original short spell had, defeating the reason ChaCha20
+	/// mode exists. So ChaCha20 mode ignores the magic spell entirely and
+	/// always draws its own 256 bits of key material from `OsRng`.
+	static ref CHACHA_KEY: [u8; 32] = {
+		let mut key = [0u8; 32];
+		OsRng.fill_bytes(&mut key);
+		key
+	};
+}
+
+/// A monotonically increasing counter used to build a collision-free
+/// ChaCha20 nonce for each encrypted literal (see [`next_nonce_suffix`]).
+static NONCE_COUNTER: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(0);
+
+/// Returns a 4-byte suffix, unique per call within this compilation, to
+/// append to [`NONCE_BASE`] so no two literals in the same build ever reuse
+/// a (key, nonce) pair under ChaCha20 — a random suffix could collide once a
+/// crate embeds enough literals (birthday bound on 32 random bits), silently
+/// leaking the XOR of two plaintexts on reuse.
+fn next_nonce_suffix() -> [u8; 4] {
+	NONCE_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed).to_le_bytes()
+}
+
+/// Which cipher the generated `litcrypt_internal` module uses to protect
+/// literals. Selected once per build via the `LITCRYPT_CIPHER` env var so
+/// that crates relying on the original XOR behavior keep working unchanged.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CipherMode {
+	Xor,
+	ChaCha20,
+}
+
+#[inline(always)]
+fn cipher_mode() -> CipherMode {
+	match env::var("LITCRYPT_CIPHER") {
+		Ok(ref mode) if mode.eq_ignore_ascii_case("chacha20") => CipherMode::ChaCha20,
+		_ => CipherMode::Xor,
+	}
+}
+
+/// Resolves the path used to reach the `litcrypt_internal` module and
+/// `LITCRYPT_ENCRYPT_KEY` static that `use_litcrypt!` generates.
+///
+/// Those items are always emitted directly into whichever crate expands
+/// `use_litcrypt!` (almost always the same crate that goes on to call
+/// `lc!`), so `crate::` finds them regardless of what `crate_name("litcrypt")`
+/// reports. In particular, `FoundCrate::Name(name)` is *not* a signal that
+/// `litcrypt` was renamed — `proc_macro_crate` returns that variant for the
+/// ordinary, non-renamed case too (whenever the invoking crate merely
+/// *depends on* litcrypt rather than being litcrypt itself), and `name` there
+/// names the `litcrypt` *dependency*, not the crate `litcrypt_internal` was
+/// generated into. Emitting `::#name::litcrypt_internal` would point at the
+/// published `litcrypt` crate, which has no such module, and break every
+/// ordinary consumer. So every outcome of the lookup still resolves to
+/// `crate::` here; `proc_macro_crate` stays wired in as the one place that
+/// would need to change if a future macro ever needs to address real items
+/// that live in the `litcrypt` crate proper.
+fn crate_path() -> TokenStream2 {
+	match crate_name("litcrypt") {
+		Ok(FoundCrate::Itself) | Ok(FoundCrate::Name(_)) | Err(_) => quote!(crate),
+	}
+}
+
 #[inline(always)]
 fn get_magic_spell() -> Vec<u8> {
 	match env::var("LITCRYPT_ENCRYPT_KEY") {
@@ -45,11 +129,32 @@ fn get_magic_spell() -> Vec<u8> {
 /// [`lc!`] macro.
 ///
 /// This key is also encrypted an  will not visible in a static analyzer.
+///
+/// Set the `LITCRYPT_CIPHER` env var to `chacha20` to protect literals with
+/// ChaCha20 instead of the default repeating-XOR. The default is unchanged
+/// so existing crates keep building without any changes. Note that under
+/// `chacha20`, `LITCRYPT_ENCRYPT_KEY` no longer sets the encryption key
+/// itself — ChaCha20 mode always generates its own fresh, independent
+/// 256-bit key, since repeating a short human-chosen spell to fill 32 bytes
+/// would have kept the exact weakness this mode exists to get away from.
+///
+/// # Limitation: re-exporting from a wrapper crate
+///
+/// `use_litcrypt!` must be invoked in the same crate that calls [`lc!`] (and
+/// the other `lc_*!` macros). If a wrapper crate calls `use_litcrypt!` and
+/// then re-exports `lc!` for its own downstream users (e.g. via a
+/// `macro_rules!` wrapper, or `pub use litcrypt::lc;`), those downstream
+/// calls fail to resolve: the generated `litcrypt_internal` module and
+/// `LITCRYPT_ENCRYPT_KEY` static live in the wrapper crate, but `lc!`
+/// expanding in a *different* crate looks for them via `crate::`, which
+/// resolves relative to whichever crate `lc!` itself expands in. There is
+/// currently no supported way to thread that path through a re-export; call
+/// `use_litcrypt!`/`lc!` directly from the crate that needs them.
 #[proc_macro]
 pub fn use_litcrypt(_tokens: TokenStream) -> TokenStream {
 	let magic_spell = get_magic_spell();
 
-	let encdec_func = quote! {
+	let xor_encdec_func = quote! {
 		pub mod litcrypt_internal {
 			// This XOR code taken from https://github.com/zummenix/xor-rs
 			/// Returns result of a XOR operation applied to a `source` byte sequence.
@@ -108,7 +213,7 @@ pub fn use_litcrypt(_tokens: TokenStream) -> TokenStream {
 				//Changing next_index function to prevent Defender from flagging it as Cobalt Strike
 				if index + 2 < count {
 				    index + 2
-				} 
+				}
 				else {
 				    if count % 2 == 0 {
 					if index + 2 == count  {
@@ -126,30 +231,320 @@ pub fn use_litcrypt(_tokens: TokenStream) -> TokenStream {
 					    1
 				      }
 				  }
-				}								
+				}
+			}
+
+			/// Like [`decrypt_bytes`], but hands back the raw decrypted bytes
+			/// instead of assuming they are valid UTF-8. Used for secrets
+			/// embedded via the `lc_bytes!` macro.
+			pub fn decrypt_bytes_raw(encrypted: &[u8], encrypt_key: &[u8]) -> Vec<u8> {
+				xor(&encrypted[..], &encrypt_key)
 			}
 
 			pub fn decrypt_bytes(encrypted: &[u8], encrypt_key: &[u8]) -> String {
-				let decrypted = xor(&encrypted[..], &encrypt_key);
-				String::from_utf8(decrypted).unwrap()
+				String::from_utf8(decrypt_bytes_raw(encrypted, encrypt_key)).unwrap()
+			}
+
+			/// Like [`decrypt_bytes`], but the resulting plaintext is scrubbed
+			/// from memory as soon as the returned [`SecretString`] is
+			/// dropped. Used by the `lc_secret!` macro.
+			pub fn decrypt_secret(encrypted: &[u8], encrypt_key: &[u8]) -> SecretString {
+				SecretString::new(decrypt_bytes(encrypted, encrypt_key))
+			}
+
+			/// A decrypted secret that overwrites its backing bytes with
+			/// zeros when dropped, so the plaintext doesn't linger in freed
+			/// memory. Derefs to `str` for ordinary use.
+			pub struct SecretString(String);
+
+			impl SecretString {
+				pub fn new(secret: String) -> SecretString {
+					SecretString(secret)
+				}
+			}
+
+			impl std::ops::Deref for SecretString {
+				type Target = str;
+				fn deref(&self) -> &str {
+					&self.0
+				}
+			}
+
+			impl Drop for SecretString {
+				fn drop(&mut self) {
+					// SAFETY: every byte is overwritten before the string is
+					// read again (it's being dropped), so transiently
+					// invalid UTF-8 is never observed.
+					for byte in unsafe { self.0.as_bytes_mut() } {
+						unsafe { std::ptr::write_volatile(byte, 0) };
+					}
+					std::sync::atomic::compiler_fence(std::sync::atomic::Ordering::SeqCst);
+				}
+			}
+
+			/// A literal that stays encrypted until [`ObfuscatedString::dec`] or
+			/// [`ObfuscatedString::dec_vec`] is called, returned by the
+			/// `lc_obf!` macro.
+			pub struct ObfuscatedString {
+				pub crypted: &'static [u8],
+				pub key: &'static [u8],
+			}
+
+			impl ObfuscatedString {
+				pub fn dec_vec(&self) -> Vec<u8> {
+					xor(self.crypted, self.key)
+				}
+
+				pub fn dec(&self) -> String {
+					String::from_utf8(self.dec_vec()).unwrap()
+				}
+			}
+
+			impl PartialEq<str> for ObfuscatedString {
+				/// Compares without ever materializing the decrypted plaintext:
+				/// XORs the stored ciphertext against the key one byte at a time
+				/// and matches the result against `other`'s bytes as it goes.
+				fn eq(&self, other: &str) -> bool {
+					let other = other.as_bytes();
+					if other.len() != self.crypted.len() {
+						return false;
+					}
+					let mut diff = 0u8;
+					for (i, &c) in self.crypted.iter().enumerate() {
+						let k = if self.key.is_empty() { 0 } else { self.key[i % self.key.len()] };
+						diff |= (c ^ k) ^ other[i];
+					}
+					diff == 0
+				}
+			}
+
+			impl PartialEq<&str> for ObfuscatedString {
+				fn eq(&self, other: &&str) -> bool {
+					self == *other
+				}
+			}
+
+			impl PartialEq<ObfuscatedString> for str {
+				fn eq(&self, other: &ObfuscatedString) -> bool {
+					other == self
+				}
+			}
+
+			impl PartialEq<ObfuscatedString> for &str {
+				fn eq(&self, other: &ObfuscatedString) -> bool {
+					other == *self
+				}
 			}
 		}
 	};
-	let result = {
-		let ekey = xor::xor(&magic_spell, b"ESJCTVgWH5HQFza7GdRx");
-		let ekey = Literal::byte_string(&ekey);
-		quote! {
-			static LITCRYPT_ENCRYPT_KEY: &'static [u8] = #ekey;
-			#encdec_func
+
+	// Mirrors `crate::chacha20`, duplicated here so the crate embedding it
+	// doesn't need a runtime dependency on litcrypt or any chacha20 crate.
+	let chacha20_encdec_func = quote! {
+		pub mod litcrypt_internal {
+			use std::convert::TryInto;
+
+			const CHACHA20_CONSTANTS: [u32; 4] = [0x61707865, 0x3320646e, 0x79622d32, 0x6b206574];
+
+			#[inline(always)]
+			fn quarter_round(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+				state[a] = state[a].wrapping_add(state[b]);
+				state[d] ^= state[a];
+				state[d] = state[d].rotate_left(16);
+
+				state[c] = state[c].wrapping_add(state[d]);
+				state[b] ^= state[c];
+				state[b] = state[b].rotate_left(12);
+
+				state[a] = state[a].wrapping_add(state[b]);
+				state[d] ^= state[a];
+				state[d] = state[d].rotate_left(8);
+
+				state[c] = state[c].wrapping_add(state[d]);
+				state[b] ^= state[c];
+				state[b] = state[b].rotate_left(7);
+			}
+
+			/// Produces one 64-byte ChaCha20 keystream block (RFC 8439, 20 rounds)
+			/// for `key`/`nonce` at the given 32-bit block `counter`.
+			fn chacha20_block(key: &[u8; 32], nonce: &[u8; 12], counter: u32) -> [u8; 64] {
+				let mut state = [0u32; 16];
+				state[0..4].copy_from_slice(&CHACHA20_CONSTANTS);
+				for i in 0..8 {
+					state[4 + i] = u32::from_le_bytes(key[i * 4..i * 4 + 4].try_into().unwrap());
+				}
+				state[12] = counter;
+				for i in 0..3 {
+					state[13 + i] = u32::from_le_bytes(nonce[i * 4..i * 4 + 4].try_into().unwrap());
+				}
+
+				let mut working = state;
+				for _ in 0..10 {
+					quarter_round(&mut working, 0, 4, 8, 12);
+					quarter_round(&mut working, 1, 5, 9, 13);
+					quarter_round(&mut working, 2, 6, 10, 14);
+					quarter_round(&mut working, 3, 7, 11, 15);
+
+					quarter_round(&mut working, 0, 5, 10, 15);
+					quarter_round(&mut working, 1, 6, 11, 12);
+					quarter_round(&mut working, 2, 7, 8, 13);
+					quarter_round(&mut working, 3, 4, 9, 14);
+				}
+
+				let mut out = [0u8; 64];
+				for i in 0..16 {
+					let word = working[i].wrapping_add(state[i]);
+					out[i * 4..i * 4 + 4].copy_from_slice(&word.to_le_bytes());
+				}
+				out
+			}
+
+			fn chacha20_xor(data: &[u8], key: &[u8; 32], nonce: &[u8; 12]) -> Vec<u8> {
+				let mut out = Vec::with_capacity(data.len());
+				for (counter, chunk) in data.chunks(64).enumerate() {
+					let keystream = chacha20_block(key, nonce, counter as u32);
+					for (byte, ks) in chunk.iter().zip(keystream.iter()) {
+						out.push(byte ^ ks);
+					}
+				}
+				out
+			}
+
+			/// Like [`decrypt_bytes`], but hands back the raw decrypted bytes
+			/// instead of assuming they are valid UTF-8. Used for secrets
+			/// embedded via the `lc_bytes!` macro.
+			pub fn decrypt_bytes_raw(encrypted: &[u8], encrypt_key: &[u8; 32], nonce: &[u8; 12]) -> Vec<u8> {
+				chacha20_xor(encrypted, encrypt_key, nonce)
+			}
+
+			pub fn decrypt_bytes(encrypted: &[u8], encrypt_key: &[u8; 32], nonce: &[u8; 12]) -> String {
+				let decrypted = decrypt_bytes_raw(encrypted, encrypt_key, nonce);
+				String::from_utf8(decrypted).unwrap()
+			}
+
+			/// Like [`decrypt_bytes`], but the resulting plaintext is scrubbed
+			/// from memory as soon as the returned [`SecretString`] is
+			/// dropped. Used by the `lc_secret!` macro.
+			pub fn decrypt_secret(encrypted: &[u8], encrypt_key: &[u8; 32], nonce: &[u8; 12]) -> SecretString {
+				SecretString::new(decrypt_bytes(encrypted, encrypt_key, nonce))
+			}
+
+			/// A decrypted secret that overwrites its backing bytes with
+			/// zeros when dropped, so the plaintext doesn't linger in freed
+			/// memory. Derefs to `str` for ordinary use.
+			pub struct SecretString(String);
+
+			impl SecretString {
+				pub fn new(secret: String) -> SecretString {
+					SecretString(secret)
+				}
+			}
+
+			impl std::ops::Deref for SecretString {
+				type Target = str;
+				fn deref(&self) -> &str {
+					&self.0
+				}
+			}
+
+			impl Drop for SecretString {
+				fn drop(&mut self) {
+					// SAFETY: every byte is overwritten before the string is
+					// read again (it's being dropped), so transiently
+					// invalid UTF-8 is never observed.
+					for byte in unsafe { self.0.as_bytes_mut() } {
+						unsafe { std::ptr::write_volatile(byte, 0) };
+					}
+					std::sync::atomic::compiler_fence(std::sync::atomic::Ordering::SeqCst);
+				}
+			}
+
+			/// A literal that stays encrypted until [`ObfuscatedString::dec`] or
+			/// [`ObfuscatedString::dec_vec`] is called, returned by the
+			/// `lc_obf!` macro.
+			pub struct ObfuscatedString {
+				pub crypted: &'static [u8],
+				pub key: &'static [u8; 32],
+				pub nonce: &'static [u8; 12],
+			}
+
+			impl ObfuscatedString {
+				pub fn dec_vec(&self) -> Vec<u8> {
+					chacha20_xor(self.crypted, self.key, self.nonce)
+				}
+
+				pub fn dec(&self) -> String {
+					String::from_utf8(self.dec_vec()).unwrap()
+				}
+			}
+
+			impl PartialEq<str> for ObfuscatedString {
+				/// Compares without ever materializing the decrypted plaintext:
+				/// generates the ChaCha20 keystream one 64-byte block at a time
+				/// and matches it against `other`'s bytes as it goes.
+				fn eq(&self, other: &str) -> bool {
+					let other = other.as_bytes();
+					if other.len() != self.crypted.len() {
+						return false;
+					}
+					let mut diff = 0u8;
+					let mut keystream = [0u8; 64];
+					for (i, &c) in self.crypted.iter().enumerate() {
+						if i % 64 == 0 {
+							keystream = chacha20_block(self.key, self.nonce, (i / 64) as u32);
+						}
+						diff |= (c ^ keystream[i % 64]) ^ other[i];
+					}
+					diff == 0
+				}
+			}
+
+			impl PartialEq<&str> for ObfuscatedString {
+				fn eq(&self, other: &&str) -> bool {
+					self == *other
+				}
+			}
+
+			impl PartialEq<ObfuscatedString> for str {
+				fn eq(&self, other: &ObfuscatedString) -> bool {
+					other == self
+				}
+			}
+
+			impl PartialEq<ObfuscatedString> for &str {
+				fn eq(&self, other: &ObfuscatedString) -> bool {
+					other == *self
+				}
+			}
 		}
 	};
+
+	let result = match cipher_mode() {
+		CipherMode::Xor => {
+			let ekey = xor::xor(&magic_spell, b"ESJCTVgWH5HQFza7GdRx");
+			let ekey = Literal::byte_string(&ekey);
+			quote! {
+				static LITCRYPT_ENCRYPT_KEY: &'static [u8] = #ekey;
+				#xor_encdec_func
+			}
+		},
+		CipherMode::ChaCha20 => {
+			let ekey = Literal::byte_string(&*CHACHA_KEY);
+			quote! {
+				static LITCRYPT_ENCRYPT_KEY: &'static [u8; 32] = #ekey;
+				#chacha20_encdec_func
+			}
+		},
+	};
 	result.into()
 }
 
-/// Encrypts the resp. string with the key set before, via calling
-/// [`use_litcrypt!`].
-#[proc_macro]
-pub fn lc(tokens: TokenStream) -> TokenStream {
+/// Pulls the quoted contents out of the last token in `tokens`, stripping
+/// the surrounding `"`s (or `"<unknown>"` if that token is not a literal).
+/// Shared by [`lc!`], [`lc_obf!`] and
+/// [`lc_secret!`], which only differ in which `encrypt_*` helper they hand
+/// the result to.
+fn literal_to_string(tokens: TokenStream) -> String {
 	let mut something = String::from("");
 	for tok in tokens {
 		something = match tok {
@@ -169,8 +564,42 @@ pub fn lc(tokens: TokenStream) -> TokenStream {
 			_ => "<unknown>".to_owned(),
 		}
 	}
+	something
+}
+
+/// Encrypts the resp. string with the key set before, via calling
+/// [`use_litcrypt!`].
+///
+/// Must be called from the same crate that called `use_litcrypt!` — see the
+/// "Limitation: re-exporting from a wrapper crate" note on [`use_litcrypt!`]
+/// for why re-exporting `lc!` from a wrapper crate doesn't work.
+#[proc_macro]
+pub fn lc(tokens: TokenStream) -> TokenStream {
+	encrypt_string(literal_to_string(tokens))
+}
 
-	encrypt_string(something)
+/// Encrypts the resp. string with the key set before, via calling
+/// [`use_litcrypt!`], and returns it as a lazily-decrypted
+/// `litcrypt_internal::ObfuscatedString` instead of a `String`.
+///
+/// Use this for secrets you only ever need to compare against, e.g.
+/// `if lc_obf!("admin") == user_input`, so the plaintext never has to be
+/// materialized in memory.
+#[proc_macro]
+pub fn lc_obf(tokens: TokenStream) -> TokenStream {
+	encrypt_string_obf(literal_to_string(tokens))
+}
+
+/// Encrypts the resp. string with the key set before, via calling
+/// [`use_litcrypt!`], and returns it as a `litcrypt_internal::SecretString`
+/// that zeroizes its plaintext on drop, instead of an ordinary `String`.
+///
+/// Use this for secrets that do need to be read as a `str` (unlike
+/// [`lc_obf!`], whose `dec()` returns an ordinary `String`) but shouldn't
+/// linger in freed memory afterwards.
+#[proc_macro]
+pub fn lc_secret(tokens: TokenStream) -> TokenStream {
+	encrypt_string_secret(literal_to_string(tokens))
 }
 
 /// Encrypts an environment variable at compile time with the key set before,
@@ -215,15 +644,158 @@ pub fn lc_dynamic(tokens: TokenStream) -> TokenStream {
 	encrypt_string(var_name)
 }
 
-fn encrypt_string(something: String) -> TokenStream {
+/// Encrypts a binary secret with the key set before, via calling
+/// [`use_litcrypt!`], and decrypts back to raw bytes instead of a `String`.
+///
+/// Accepts either a string literal or a byte-string literal (`b"..."`), so
+/// secrets that aren't valid UTF-8 (a key, a token, a DER blob) can be
+/// embedded without the panic [`lc!`] would hit trying to decode them.
+#[proc_macro]
+pub fn lc_bytes(tokens: TokenStream) -> TokenStream {
+	let expr = parse_macro_input!(tokens as Expr);
+	let something = match expr {
+		Expr::Lit(ExprLit { lit: Lit::Str(lit_str), .. }) => lit_str.value().into_bytes(),
+		Expr::Lit(ExprLit { lit: Lit::ByteStr(lit_bytes), .. }) => lit_bytes.value(),
+		_ => b"<unknown>".to_vec(),
+	};
+
+	encrypt_bytes(something)
+}
+
+/// Result of encrypting a payload under whichever cipher [`cipher_mode`]
+/// selects: the ciphertext literal, plus the nonce literal if the cipher
+/// needs one (`ChaCha20`; `Xor` has none).
+struct EncryptedPayload {
+	encrypted: Literal,
+	nonce: Option<Literal>,
+}
+
+/// Encrypts `something` under the configured [`CipherMode`] — XOR keyed by
+/// the current magic spell, or ChaCha20 keyed by [`CHACHA_KEY`]. Shared by
+/// every `encrypt_*` helper below so the cipher-mode match lives in exactly
+/// one place.
+fn encrypt_payload(something: &[u8]) -> EncryptedPayload {
 	let magic_spell = get_magic_spell();
-	let encrypt_key = xor::xor(&magic_spell, b"ESJCTVgWH5HQFza7GdRx");
-	let encrypted = xor::xor(&something.as_bytes(), &encrypt_key);
-	let encrypted = Literal::byte_string(&encrypted);
 
-	let result = quote! {
-		crate::litcrypt_internal::decrypt_bytes(#encrypted, crate::LITCRYPT_ENCRYPT_KEY)
+	match cipher_mode() {
+		CipherMode::Xor => {
+			let encrypt_key = xor::xor(&magic_spell, b"ESJCTVgWH5HQFza7GdRx");
+			let encrypted = xor::xor(something, &encrypt_key);
+
+			EncryptedPayload {
+				encrypted: Literal::byte_string(&encrypted),
+				nonce: None,
+			}
+		},
+		CipherMode::ChaCha20 => {
+			let mut nonce = [0u8; 12];
+			nonce[..8].copy_from_slice(&*NONCE_BASE);
+			nonce[8..].copy_from_slice(&next_nonce_suffix());
+
+			let encrypted = chacha20::xor(something, &*CHACHA_KEY, &nonce);
+
+			EncryptedPayload {
+				encrypted: Literal::byte_string(&encrypted),
+				nonce: Some(Literal::byte_string(&nonce)),
+			}
+		},
+	}
+}
+
+fn encrypt_string(something: String) -> TokenStream {
+	let krate = crate_path();
+	let payload = encrypt_payload(something.as_bytes());
+	let encrypted = payload.encrypted;
+
+	let result = match payload.nonce {
+		None => quote! {
+			#krate::litcrypt_internal::decrypt_bytes(#encrypted, #krate::LITCRYPT_ENCRYPT_KEY)
+		},
+		Some(nonce) => quote! {
+			#krate::litcrypt_internal::decrypt_bytes(#encrypted, #krate::LITCRYPT_ENCRYPT_KEY, #nonce)
+		},
+	};
+
+	result.into()
+}
+
+fn encrypt_bytes(something: Vec<u8>) -> TokenStream {
+	let krate = crate_path();
+	let payload = encrypt_payload(&something);
+	let encrypted = payload.encrypted;
+
+	let result = match payload.nonce {
+		None => quote! {
+			#krate::litcrypt_internal::decrypt_bytes_raw(#encrypted, #krate::LITCRYPT_ENCRYPT_KEY)
+		},
+		Some(nonce) => quote! {
+			#krate::litcrypt_internal::decrypt_bytes_raw(#encrypted, #krate::LITCRYPT_ENCRYPT_KEY, #nonce)
+		},
+	};
+
+	result.into()
+}
+
+fn encrypt_string_secret(something: String) -> TokenStream {
+	let krate = crate_path();
+	let payload = encrypt_payload(something.as_bytes());
+	let encrypted = payload.encrypted;
+
+	let result = match payload.nonce {
+		None => quote! {
+			#krate::litcrypt_internal::decrypt_secret(#encrypted, #krate::LITCRYPT_ENCRYPT_KEY)
+		},
+		Some(nonce) => quote! {
+			#krate::litcrypt_internal::decrypt_secret(#encrypted, #krate::LITCRYPT_ENCRYPT_KEY, #nonce)
+		},
+	};
+
+	result.into()
+}
+
+fn encrypt_string_obf(something: String) -> TokenStream {
+	let krate = crate_path();
+	let payload = encrypt_payload(something.as_bytes());
+	let encrypted = payload.encrypted;
+
+	let result = match payload.nonce {
+		None => quote! {
+			#krate::litcrypt_internal::ObfuscatedString {
+				crypted: #encrypted,
+				key: #krate::LITCRYPT_ENCRYPT_KEY,
+			}
+		},
+		Some(nonce) => quote! {
+			#krate::litcrypt_internal::ObfuscatedString {
+				crypted: #encrypted,
+				key: #krate::LITCRYPT_ENCRYPT_KEY,
+				nonce: #nonce,
+			}
+		},
 	};
 
 	result.into()
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use expectest::prelude::*;
+
+	#[test]
+	fn chacha_key_is_generated_once_and_reused() {
+		// `CHACHA_KEY` is a `lazy_static`, so repeated reads within the same
+		// compilation must return the exact same bytes (every `lc!` call
+		// embeds the one key `use_litcrypt!` generated).
+		expect!(*CHACHA_KEY).to(be_equal_to(*CHACHA_KEY));
+	}
+
+	#[test]
+	fn nonce_suffixes_never_repeat_within_a_compilation() {
+		let suffixes: Vec<u32> = (0..100).map(|_| u32::from_le_bytes(next_nonce_suffix())).collect();
+		let mut sorted = suffixes.clone();
+		sorted.sort_unstable();
+		sorted.dedup();
+		expect!(sorted.len()).to(be_equal_to(suffixes.len()));
+	}
+}
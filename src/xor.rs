@@ -0,0 +1,76 @@
+//! Minimal repeating-key XOR, used by the proc-macro itself to obfuscate the
+//! encryption key and (in `xor` cipher mode) the literal before it is baked
+//! into the caller's binary as a byte string.
+//!
+//! Taken and adapted from https://github.com/zummenix/xor-rs
+
+/// Returns result of a XOR operation applied to a `source` byte sequence.
+///
+/// `key` will be an infinitely repeating byte sequence.
+pub fn xor(source: &[u8], key: &[u8]) -> Vec<u8> {
+	match key.len() {
+		0 => source.into(),
+		1 => xor_with_byte(source, key[0]),
+		_ => {
+			let key_iter = InfiniteByteIterator::new(key);
+			source.iter().zip(key_iter).map(|(&a, b)| a ^ b).collect()
+		}
+	}
+}
+
+/// Returns result of a XOR operation applied to a `source` byte sequence.
+///
+/// `byte` will be an infinitely repeating byte sequence.
+pub fn xor_with_byte(source: &[u8], byte: u8) -> Vec<u8> {
+	source.iter().map(|&a| a ^ byte).collect()
+}
+
+struct InfiniteByteIterator<'a> {
+	bytes: &'a [u8],
+	index: usize,
+}
+
+impl<'a> InfiniteByteIterator<'a> {
+	fn new(bytes: &'a [u8]) -> InfiniteByteIterator<'a> {
+		InfiniteByteIterator { bytes, index: 0 }
+	}
+}
+
+impl<'a> Iterator for InfiniteByteIterator<'a> {
+	type Item = u8;
+	fn next(&mut self) -> Option<u8> {
+		let byte = self.bytes[self.index];
+		self.index = (self.index + 1) % self.bytes.len();
+		Some(byte)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use expectest::prelude::*;
+
+	#[test]
+	fn xor_is_its_own_inverse() {
+		let key = b"a repeating key";
+		let original = b"the quick brown fox jumps over the lazy dog".to_vec();
+
+		let encrypted = xor(&original, key);
+		expect!(encrypted).to_not(be_equal_to(original.clone()));
+
+		let decrypted = xor(&xor(&original, key), key);
+		expect!(decrypted).to(be_equal_to(original));
+	}
+
+	#[test]
+	fn empty_key_is_a_no_op() {
+		let original = b"unchanged".to_vec();
+		expect!(xor(&original, b"")).to(be_equal_to(original));
+	}
+
+	#[test]
+	fn single_byte_key_matches_xor_with_byte() {
+		let original = b"some data".to_vec();
+		expect!(xor(&original, &[0x42])).to(be_equal_to(xor_with_byte(&original, 0x42)));
+	}
+}